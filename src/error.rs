@@ -0,0 +1,49 @@
+use crate::solace::ffi;
+use crate::SolClientReturnCode;
+use enum_primitive::*;
+use std::ffi::CStr;
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, SolaceError>;
+
+/// An error from this crate. A solClient failure (`SolClient`) carries the
+/// return code, subcode, and human-readable detail string; return codes other
+/// than `Ok` and `NotFound` are reported as `Fail`.
+#[derive(Error, Debug)]
+pub enum SolaceError {
+    #[error("solClient returned {rc:?} (subcode {subcode}, response code {responsecode}): {detail}")]
+    SolClient {
+        rc: SolClientReturnCode,
+        subcode: i32,
+        responsecode: i32,
+        detail: String,
+    },
+    #[error("invalid argument: {0}")]
+    InvalidArgument(#[from] std::ffi::NulError),
+    #[error("unsupported SDT field type: {0}")]
+    UnsupportedFieldType(u32),
+}
+
+// Pulls the thread-local last-error info solClient populates alongside a
+// non-Ok return code and turns it into a `SolaceError::SolClient`.
+pub(crate) fn last_error(rc: SolClientReturnCode) -> SolaceError {
+    let error_info = unsafe { &*ffi::solClient_getLastErrorInfo() };
+
+    let subcode_str = unsafe { CStr::from_ptr(ffi::solClient_subcodeToString(error_info.subCode)) }
+        .to_string_lossy();
+    let error_str = unsafe { CStr::from_ptr(error_info.errorStr.as_ptr()) }.to_string_lossy();
+
+    SolaceError::SolClient {
+        rc,
+        subcode: error_info.subCode as i32,
+        responsecode: error_info.responseCode,
+        detail: format!("{subcode_str}: {error_str}"),
+    }
+}
+
+// Parses a raw solClient return code, falling back to `Fail` if it's not a
+// code this crate knows about, so `last_error` always has something to
+// report.
+pub(crate) fn parsed_rc(raw_rc: i32) -> SolClientReturnCode {
+    SolClientReturnCode::from_i32(raw_rc).unwrap_or(SolClientReturnCode::Fail)
+}
@@ -0,0 +1,36 @@
+mod context;
+pub mod error;
+pub mod message;
+mod solace;
+pub mod session;
+
+pub use error::{Result, SolaceError};
+
+use enum_primitive::*;
+use solace::ffi;
+use thiserror::Error;
+
+enum_from_primitive! {
+    /// The `solClient_returnCode_t` values this crate distinguishes; other
+    /// codes have no variant here.
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    #[repr(i32)]
+    pub enum SolClientReturnCode {
+        Ok = ffi::SOLCLIENT_OK,
+        Fail = ffi::SOLCLIENT_FAIL,
+        NotFound = ffi::SOLCLIENT_NOT_FOUND,
+    }
+}
+
+/// Errors a [`session::Session`] operation can fail with.
+#[derive(Error, Debug)]
+pub enum SessionError {
+    #[error("solClient session failure: {0}")]
+    SolClient(#[from] SolaceError),
+    #[error("invalid argument: {0}")]
+    InvalidArgument(#[from] std::ffi::NulError),
+    #[error("failed to subscribe to topic: {0}")]
+    SubscriptionFailure(String),
+    #[error("failed to unsubscribe from topic: {0}")]
+    UnsubscriptionFailure(String),
+}
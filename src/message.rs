@@ -1,13 +1,17 @@
 pub mod destination;
 pub mod inbound;
 pub mod outbound;
+pub mod sdt;
 
+use crate::error::{last_error, parsed_rc, Result};
 use crate::solace::ffi;
-use crate::{Result, SolClientReturnCode, SolaceError};
+use crate::SolClientReturnCode;
+use std::collections::HashMap;
 pub use destination::{DestinationType, MessageDestination};
 use enum_primitive::*;
 pub use inbound::InboundMessage;
 pub use outbound::{OutboundMessage, OutboundMessageBuilder};
+pub use sdt::SdtValue;
 use std::ffi::CStr;
 use std::mem;
 use std::ptr;
@@ -64,8 +68,7 @@ pub trait Message<'a> {
         };
 
         if SolClientReturnCode::from_i32(msg_ops_result) != Some(SolClientReturnCode::Ok) {
-            println!("solace did not return ok; code: {}", msg_ops_result);
-            return Err(SolaceError);
+            return Err(last_error(parsed_rc(msg_ops_result)));
         }
         let buf_len = buffer_len.try_into().unwrap();
 
@@ -81,20 +84,18 @@ pub trait Message<'a> {
 
         let mut buffer = ptr::null();
 
-        println!("pointing the buffer to the binary attachment");
         let msg_ops_result = unsafe {
             ffi::solClient_msg_getBinaryAttachmentString(self.get_raw_message_ptr(), &mut buffer)
         };
 
         if SolClientReturnCode::from_i32(msg_ops_result) != Some(SolClientReturnCode::Ok) {
-            println!("solace did not return ok");
-            return Err(SolaceError);
+            return Err(last_error(parsed_rc(msg_ops_result)));
         }
 
-        println!("successfully pointed the buffer to the binary attachment");
-
         let c_str = unsafe { CStr::from_ptr(buffer) };
-        return c_str.to_str().map_err(|_| SolaceError);
+        c_str
+            .to_str()
+            .map_err(|_| last_error(SolClientReturnCode::Fail))
     }
     fn get_application_message_id(&'a self) -> Result<&'a str> {
         todo!()
@@ -106,6 +107,49 @@ pub trait Message<'a> {
         todo!()
     }
 
+    /// Reads the message's binary attachment as a Structured Data Type map,
+    /// deep-copying every field into an owned [`SdtValue`].
+    fn get_sdt_map(&'a self) -> Result<HashMap<String, SdtValue>> {
+        let mut container: ffi::solClient_opaqueContainer_pt = ptr::null_mut();
+        let msg_ops_result = unsafe {
+            ffi::solClient_msg_getBinaryAttachmentMap(self.get_raw_message_ptr(), &mut container)
+        };
+
+        if SolClientReturnCode::from_i32(msg_ops_result) != Some(SolClientReturnCode::Ok) {
+            return Err(last_error(parsed_rc(msg_ops_result)));
+        }
+
+        // however the read goes, the container handle must always be closed.
+        let read_result = sdt::read_map(container);
+        let close_rc = unsafe { ffi::solClient_container_closeMapStream(&mut container) };
+        let fields = read_result?;
+        if SolClientReturnCode::from_i32(close_rc) != Some(SolClientReturnCode::Ok) {
+            return Err(last_error(parsed_rc(close_rc)));
+        }
+        Ok(fields)
+    }
+
+    /// Reads the message's binary attachment as a Structured Data Type
+    /// stream, deep-copying every field into an owned [`SdtValue`].
+    fn get_sdt_stream(&'a self) -> Result<Vec<SdtValue>> {
+        let mut container: ffi::solClient_opaqueContainer_pt = ptr::null_mut();
+        let msg_ops_result = unsafe {
+            ffi::solClient_msg_getBinaryAttachmentStream(self.get_raw_message_ptr(), &mut container)
+        };
+
+        if SolClientReturnCode::from_i32(msg_ops_result) != Some(SolClientReturnCode::Ok) {
+            return Err(last_error(parsed_rc(msg_ops_result)));
+        }
+
+        let read_result = sdt::read_stream(container);
+        let close_rc = unsafe { ffi::solClient_container_closeMapStream(&mut container) };
+        let items = read_result?;
+        if SolClientReturnCode::from_i32(close_rc) != Some(SolClientReturnCode::Ok) {
+            return Err(last_error(parsed_rc(close_rc)));
+        }
+        Ok(items)
+    }
+
     fn get_correlation_id(&'a self) -> Result<&'a str> {
         let mut buffer = ptr::null();
 
@@ -113,11 +157,13 @@ pub trait Message<'a> {
             unsafe { ffi::solClient_msg_getCorrelationId(self.get_raw_message_ptr(), &mut buffer) };
 
         if SolClientReturnCode::from_i32(msg_ops_result) != Some(SolClientReturnCode::Ok) {
-            return Err(SolaceError);
+            return Err(last_error(parsed_rc(msg_ops_result)));
         }
 
         let c_str = unsafe { CStr::from_ptr(buffer) };
-        return c_str.to_str().map_err(|_| SolaceError);
+        c_str
+            .to_str()
+            .map_err(|_| last_error(SolClientReturnCode::Fail))
     }
     fn get_expiration(&'a self) -> Result<SystemTime> {
         todo!()
@@ -143,14 +189,11 @@ pub trait Message<'a> {
             )
         };
         if SolClientReturnCode::from_i32(msg_ops_result) == Some(SolClientReturnCode::NotFound) {
-            println!("destination was not found");
             return Ok(None);
         }
 
-        println!("message returned: {}", msg_ops_result);
         if SolClientReturnCode::from_i32(msg_ops_result) == Some(SolClientReturnCode::Fail) {
-            println!("solace did not return ok");
-            return Err(SolaceError);
+            return Err(last_error(SolClientReturnCode::Fail));
         }
 
         Ok(Some(MessageDestination::from(dest_struct)))
@@ -1,8 +1,10 @@
 use super::destination::MessageDestination;
+use super::sdt::{self, SdtValue};
 use super::{ClassOfService, DeliveryMode, Message};
 use crate::solace::ffi;
-use crate::SolClientReturnCode;
+use crate::{SolClientReturnCode, SolaceError};
 use num_traits::FromPrimitive;
+use std::collections::HashMap;
 use std::ffi::{CString, NulError};
 use std::ptr;
 use thiserror::Error;
@@ -17,6 +19,10 @@ pub enum MessageBuilderError {
     SolClientError,
     #[error("solClient message aloc failed")]
     MessageAlocFailure,
+    #[error("failed to write SDT payload: {0}")]
+    SdtWriteFailure(#[from] SolaceError),
+    #[error("conflicting args: {0}")]
+    ConflictingArgs(String),
 }
 
 type Result<T> = std::result::Result<T, MessageBuilderError>;
@@ -44,6 +50,8 @@ pub struct OutboundMessageBuilder {
     delivery_mode: Option<DeliveryMode>,
     destination: Option<MessageDestination>,
     message: Option<CString>,
+    sdt_map: Option<HashMap<String, SdtValue>>,
+    sdt_stream: Option<Vec<SdtValue>>,
     correlation_id: Option<CString>,
     class_of_service: Option<ClassOfService>,
 }
@@ -55,6 +63,8 @@ impl OutboundMessageBuilder {
             delivery_mode: None,
             destination: None,
             message: None,
+            sdt_map: None,
+            sdt_stream: None,
             correlation_id: None,
             class_of_service: None,
         }
@@ -98,6 +108,23 @@ impl OutboundMessageBuilder {
         todo!();
     }
 
+    /// Sets the message's payload to a Structured Data Type map, letting the
+    /// caller build typed, self-describing messages instead of a single
+    /// binary/string attachment. Mutually exclusive with `set_binary_string`
+    /// and `set_sdt_stream`.
+    pub fn set_sdt_map(mut self, map: HashMap<String, SdtValue>) -> Self {
+        self.sdt_map = Some(map);
+        self
+    }
+
+    /// Sets the message's payload to a Structured Data Type stream, the
+    /// positional counterpart to `set_sdt_map`. Mutually exclusive with
+    /// `set_binary_string` and `set_sdt_map`.
+    pub fn set_sdt_stream(mut self, items: Vec<SdtValue>) -> Self {
+        self.sdt_stream = Some(items);
+        self
+    }
+
     pub fn set_correlation_id<M>(mut self, id: M) -> Result<Self>
     where
         M: Into<Vec<u8>>,
@@ -143,17 +170,56 @@ impl OutboundMessageBuilder {
             return Err(MessageBuilderError::SolClientError);
         };
 
-        // I thought we would have passed ownership to the c function
-        // but we are passing a reference to the c function instead
-        let Some(message) = self.message else{
-            return Err(MessageBuilderError::MissingArgs("message".to_owned()));
-        };
-        let set_attachment_result =
-            unsafe { ffi::solClient_msg_setBinaryAttachmentString(msg_ptr, message.as_ptr()) };
+        match (self.message, self.sdt_map, self.sdt_stream) {
+            (Some(message), None, None) => {
+                // I thought we would have passed ownership to the c function
+                // but we are passing a reference to the c function instead
+                let set_attachment_result =
+                    unsafe { ffi::solClient_msg_setBinaryAttachmentString(msg_ptr, message.as_ptr()) };
 
-        let Some(SolClientReturnCode::Ok) = SolClientReturnCode::from_i32(set_attachment_result) else{
-            return Err(MessageBuilderError::SolClientError);
-        };
+                let Some(SolClientReturnCode::Ok) = SolClientReturnCode::from_i32(set_attachment_result) else{
+                    return Err(MessageBuilderError::SolClientError);
+                };
+            }
+            (None, Some(map), None) => {
+                let mut container: ffi::solClient_opaqueContainer_pt = ptr::null_mut();
+                let open_result =
+                    unsafe { ffi::solClient_msg_createBinaryAttachmentMap(msg_ptr, &mut container) };
+                let Some(SolClientReturnCode::Ok) = SolClientReturnCode::from_i32(open_result) else{
+                    return Err(MessageBuilderError::SolClientError);
+                };
+
+                let write_result = sdt::write_map(container, &map);
+                let close_result = unsafe { ffi::solClient_container_closeMapStream(&mut container) };
+                write_result?;
+                let Some(SolClientReturnCode::Ok) = SolClientReturnCode::from_i32(close_result) else{
+                    return Err(MessageBuilderError::SolClientError);
+                };
+            }
+            (None, None, Some(items)) => {
+                let mut container: ffi::solClient_opaqueContainer_pt = ptr::null_mut();
+                let open_result =
+                    unsafe { ffi::solClient_msg_createBinaryAttachmentStream(msg_ptr, &mut container) };
+                let Some(SolClientReturnCode::Ok) = SolClientReturnCode::from_i32(open_result) else{
+                    return Err(MessageBuilderError::SolClientError);
+                };
+
+                let write_result = sdt::write_stream(container, &items);
+                let close_result = unsafe { ffi::solClient_container_closeMapStream(&mut container) };
+                write_result?;
+                let Some(SolClientReturnCode::Ok) = SolClientReturnCode::from_i32(close_result) else{
+                    return Err(MessageBuilderError::SolClientError);
+                };
+            }
+            (None, None, None) => {
+                return Err(MessageBuilderError::MissingArgs("message".to_owned()));
+            }
+            _ => {
+                return Err(MessageBuilderError::ConflictingArgs(
+                    "message, sdt_map, and sdt_stream are mutually exclusive".to_owned(),
+                ));
+            }
+        }
 
         if let Some(id) = self.correlation_id {
             let set_correlation_id_result =
@@ -229,4 +295,59 @@ mod tests {
 
         assert!("test_correlation" == correlation_id);
     }
+
+    #[test]
+    fn it_should_build_with_same_sdt_map() {
+        let dest = MessageDestination::new(DestinationType::Topic, "test_topic").unwrap();
+
+        let mut nested = HashMap::new();
+        nested.insert("inner_bool".to_owned(), SdtValue::Bool(true));
+        nested.insert("inner_string".to_owned(), SdtValue::String("nested".to_owned()));
+
+        let mut map = HashMap::new();
+        map.insert("a_string".to_owned(), SdtValue::String("hello".to_owned()));
+        map.insert("an_int".to_owned(), SdtValue::Int32(42));
+        map.insert("a_map".to_owned(), SdtValue::Map(nested));
+        map.insert(
+            "a_stream".to_owned(),
+            SdtValue::Stream(vec![SdtValue::Int32(1), SdtValue::Int32(2), SdtValue::Null]),
+        );
+
+        let message = OutboundMessageBuilder::new()
+            .set_delivery_mode(DeliveryMode::Direct)
+            .set_destination(dest)
+            .set_sdt_map(map.clone())
+            .build()
+            .unwrap();
+
+        let read_back = message.get_sdt_map().unwrap();
+
+        assert_eq!(map, read_back);
+    }
+
+    #[test]
+    fn it_should_build_with_same_sdt_stream() {
+        let dest = MessageDestination::new(DestinationType::Topic, "test_topic").unwrap();
+
+        let mut nested = HashMap::new();
+        nested.insert("inner_string".to_owned(), SdtValue::String("nested".to_owned()));
+
+        let items = vec![
+            SdtValue::Int32(1),
+            SdtValue::String("two".to_owned()),
+            SdtValue::Map(nested),
+            SdtValue::Stream(vec![SdtValue::Bool(true), SdtValue::Null]),
+        ];
+
+        let message = OutboundMessageBuilder::new()
+            .set_delivery_mode(DeliveryMode::Direct)
+            .set_destination(dest)
+            .set_sdt_stream(items.clone())
+            .build()
+            .unwrap();
+
+        let read_back = message.get_sdt_stream().unwrap();
+
+        assert_eq!(items, read_back);
+    }
 }
@@ -0,0 +1,241 @@
+use crate::error::{last_error, parsed_rc, Result, SolaceError};
+use crate::solace::ffi;
+use crate::SolClientReturnCode;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+/// An owned Solace Structured Data Type (SDT) value.
+///
+/// Covers the `solClient_fieldType_t` types listed below; other field types
+/// (e.g. Char, Destination) are read as `SolaceError::UnsupportedFieldType`.
+/// Everything is deep-copied out of the
+/// container as soon as it's read, in keeping with the crate's policy of
+/// never assuming ownership of a C buffer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SdtValue {
+    Bool(bool),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Uint8(u8),
+    Uint16(u16),
+    Uint32(u32),
+    Uint64(u64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    ByteArray(Vec<u8>),
+    Map(HashMap<String, SdtValue>),
+    Stream(Vec<SdtValue>),
+    Null,
+}
+
+impl SdtValue {
+    // Writes this value into `container`, keyed by `name` for a map field or
+    // positionally (`name` is `None`) for a stream field.
+    fn write_into(&self, container: ffi::solClient_opaqueContainer_pt, name: Option<&CStr>) -> Result<()> {
+        let name_ptr = name.map_or(ptr::null(), CStr::as_ptr);
+
+        let rc = match self {
+            SdtValue::Bool(v) => unsafe {
+                ffi::solClient_container_addBoolean(container, *v as ffi::solClient_bool_t, name_ptr)
+            },
+            SdtValue::Int8(v) => unsafe { ffi::solClient_container_addInt8(container, *v, name_ptr) },
+            SdtValue::Int16(v) => unsafe { ffi::solClient_container_addInt16(container, *v, name_ptr) },
+            SdtValue::Int32(v) => unsafe { ffi::solClient_container_addInt32(container, *v, name_ptr) },
+            SdtValue::Int64(v) => unsafe { ffi::solClient_container_addInt64(container, *v, name_ptr) },
+            SdtValue::Uint8(v) => unsafe { ffi::solClient_container_addUint8(container, *v, name_ptr) },
+            SdtValue::Uint16(v) => unsafe { ffi::solClient_container_addUint16(container, *v, name_ptr) },
+            SdtValue::Uint32(v) => unsafe { ffi::solClient_container_addUint32(container, *v, name_ptr) },
+            SdtValue::Uint64(v) => unsafe { ffi::solClient_container_addUint64(container, *v, name_ptr) },
+            SdtValue::Float(v) => unsafe { ffi::solClient_container_addFloat(container, *v, name_ptr) },
+            SdtValue::Double(v) => unsafe { ffi::solClient_container_addDouble(container, *v, name_ptr) },
+            SdtValue::String(v) => {
+                let c_value = CString::new(v.as_str())?;
+                unsafe { ffi::solClient_container_addString(container, c_value.as_ptr(), name_ptr) }
+            }
+            SdtValue::ByteArray(v) => unsafe {
+                ffi::solClient_container_addBinary(container, v.as_ptr(), v.len() as u32, name_ptr)
+            },
+            SdtValue::Null => unsafe { ffi::solClient_container_addNull(container, name_ptr) },
+            SdtValue::Map(fields) => return write_nested_map(container, name_ptr, fields),
+            SdtValue::Stream(items) => return write_nested_stream(container, name_ptr, items),
+        };
+
+        if SolClientReturnCode::from_i32(rc) != Some(SolClientReturnCode::Ok) {
+            return Err(last_error(parsed_rc(rc)));
+        }
+        Ok(())
+    }
+}
+
+fn write_nested_map(
+    container: ffi::solClient_opaqueContainer_pt,
+    name_ptr: *const std::os::raw::c_char,
+    fields: &HashMap<String, SdtValue>,
+) -> Result<()> {
+    let mut sub_container: ffi::solClient_opaqueContainer_pt = ptr::null_mut();
+    let open_rc = unsafe { ffi::solClient_container_addMap(container, &mut sub_container, name_ptr) };
+    if SolClientReturnCode::from_i32(open_rc) != Some(SolClientReturnCode::Ok) {
+        return Err(last_error(parsed_rc(open_rc)));
+    }
+
+    // however the write goes, the sub-container must always be closed.
+    let write_result = write_map(sub_container, fields);
+    let close_rc = unsafe { ffi::solClient_container_closeMapStream(&mut sub_container) };
+    write_result?;
+    if SolClientReturnCode::from_i32(close_rc) != Some(SolClientReturnCode::Ok) {
+        return Err(last_error(parsed_rc(close_rc)));
+    }
+    Ok(())
+}
+
+fn write_nested_stream(
+    container: ffi::solClient_opaqueContainer_pt,
+    name_ptr: *const std::os::raw::c_char,
+    items: &[SdtValue],
+) -> Result<()> {
+    let mut sub_container: ffi::solClient_opaqueContainer_pt = ptr::null_mut();
+    let open_rc = unsafe { ffi::solClient_container_addStream(container, &mut sub_container, name_ptr) };
+    if SolClientReturnCode::from_i32(open_rc) != Some(SolClientReturnCode::Ok) {
+        return Err(last_error(parsed_rc(open_rc)));
+    }
+
+    let write_result = write_stream(sub_container, items);
+    let close_rc = unsafe { ffi::solClient_container_closeMapStream(&mut sub_container) };
+    write_result?;
+    if SolClientReturnCode::from_i32(close_rc) != Some(SolClientReturnCode::Ok) {
+        return Err(last_error(parsed_rc(close_rc)));
+    }
+    Ok(())
+}
+
+pub(crate) fn write_map(
+    container: ffi::solClient_opaqueContainer_pt,
+    fields: &HashMap<String, SdtValue>,
+) -> Result<()> {
+    for (key, value) in fields {
+        let c_key = CString::new(key.as_str())?;
+        value.write_into(container, Some(&c_key))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn write_stream(container: ffi::solClient_opaqueContainer_pt, items: &[SdtValue]) -> Result<()> {
+    for value in items {
+        value.write_into(container, None)?;
+    }
+    Ok(())
+}
+
+fn read_field(field: &ffi::solClient_field_t) -> Result<SdtValue> {
+    match field.type_ {
+        ffi::SOLCLIENT_FIELD_BOOL => Ok(SdtValue::Bool(unsafe { field.value.boolean } != 0)),
+        ffi::SOLCLIENT_FIELD_INT8 => Ok(SdtValue::Int8(unsafe { field.value.int8 })),
+        ffi::SOLCLIENT_FIELD_INT16 => Ok(SdtValue::Int16(unsafe { field.value.int16 })),
+        ffi::SOLCLIENT_FIELD_INT32 => Ok(SdtValue::Int32(unsafe { field.value.int32 })),
+        ffi::SOLCLIENT_FIELD_INT64 => Ok(SdtValue::Int64(unsafe { field.value.int64 })),
+        ffi::SOLCLIENT_FIELD_UINT8 => Ok(SdtValue::Uint8(unsafe { field.value.uint8 })),
+        ffi::SOLCLIENT_FIELD_UINT16 => Ok(SdtValue::Uint16(unsafe { field.value.uint16 })),
+        ffi::SOLCLIENT_FIELD_UINT32 => Ok(SdtValue::Uint32(unsafe { field.value.uint32 })),
+        ffi::SOLCLIENT_FIELD_UINT64 => Ok(SdtValue::Uint64(unsafe { field.value.uint64 })),
+        ffi::SOLCLIENT_FIELD_FLOAT => Ok(SdtValue::Float(unsafe { field.value.float32 })),
+        ffi::SOLCLIENT_FIELD_DOUBLE => Ok(SdtValue::Double(unsafe { field.value.float64 })),
+        ffi::SOLCLIENT_FIELD_NULL => Ok(SdtValue::Null),
+        ffi::SOLCLIENT_FIELD_STRING => {
+            let c_str = unsafe { CStr::from_ptr(field.value.string) };
+            Ok(SdtValue::String(c_str.to_string_lossy().into_owned()))
+        }
+        ffi::SOLCLIENT_FIELD_BYTEARRAY => {
+            let slice = unsafe {
+                std::slice::from_raw_parts(field.value.bytearray as *const u8, field.length as usize)
+            };
+            Ok(SdtValue::ByteArray(slice.to_vec()))
+        }
+        ffi::SOLCLIENT_FIELD_MAP => {
+            let mut sub_container: ffi::solClient_opaqueContainer_pt = ptr::null_mut();
+            let rc = unsafe { ffi::solClient_field_getMap(field, &mut sub_container) };
+            if SolClientReturnCode::from_i32(rc) != Some(SolClientReturnCode::Ok) {
+                return Err(last_error(parsed_rc(rc)));
+            }
+
+            // however the read goes, the sub-container must always be closed.
+            let read_result = read_map(sub_container);
+            let close_rc = unsafe { ffi::solClient_container_closeMapStream(&mut sub_container) };
+            let fields = read_result?;
+            if SolClientReturnCode::from_i32(close_rc) != Some(SolClientReturnCode::Ok) {
+                return Err(last_error(parsed_rc(close_rc)));
+            }
+            Ok(SdtValue::Map(fields))
+        }
+        ffi::SOLCLIENT_FIELD_STREAM => {
+            let mut sub_container: ffi::solClient_opaqueContainer_pt = ptr::null_mut();
+            let rc = unsafe { ffi::solClient_field_getStream(field, &mut sub_container) };
+            if SolClientReturnCode::from_i32(rc) != Some(SolClientReturnCode::Ok) {
+                return Err(last_error(parsed_rc(rc)));
+            }
+
+            let read_result = read_stream(sub_container);
+            let close_rc = unsafe { ffi::solClient_container_closeMapStream(&mut sub_container) };
+            let items = read_result?;
+            if SolClientReturnCode::from_i32(close_rc) != Some(SolClientReturnCode::Ok) {
+                return Err(last_error(parsed_rc(close_rc)));
+            }
+            Ok(SdtValue::Stream(items))
+        }
+        // A field type solClient knows about but this enum doesn't model yet
+        // (e.g. Destination, Char, SMF). Surface it as an error instead of
+        // silently coercing it to `Null`, which would be indistinguishable
+        // from an actual null field and would quietly drop the real data.
+        other => Err(SolaceError::UnsupportedFieldType(other as u32)),
+    }
+}
+
+pub(crate) fn read_map(container: ffi::solClient_opaqueContainer_pt) -> Result<HashMap<String, SdtValue>> {
+    let mut fields = HashMap::new();
+    loop {
+        let mut field: ffi::solClient_field_t = unsafe { std::mem::zeroed() };
+        let rc = unsafe {
+            ffi::solClient_container_getNextField(
+                container,
+                &mut field,
+                std::mem::size_of::<ffi::solClient_field_t>(),
+            )
+        };
+        if SolClientReturnCode::from_i32(rc) == Some(SolClientReturnCode::NotFound) {
+            break;
+        }
+        if SolClientReturnCode::from_i32(rc) != Some(SolClientReturnCode::Ok) {
+            return Err(last_error(parsed_rc(rc)));
+        }
+
+        let name = unsafe { CStr::from_ptr(field.name_p) }.to_string_lossy().into_owned();
+        fields.insert(name, read_field(&field)?);
+    }
+    Ok(fields)
+}
+
+pub(crate) fn read_stream(container: ffi::solClient_opaqueContainer_pt) -> Result<Vec<SdtValue>> {
+    let mut items = Vec::new();
+    loop {
+        let mut field: ffi::solClient_field_t = unsafe { std::mem::zeroed() };
+        let rc = unsafe {
+            ffi::solClient_container_getNextField(
+                container,
+                &mut field,
+                std::mem::size_of::<ffi::solClient_field_t>(),
+            )
+        };
+        if SolClientReturnCode::from_i32(rc) == Some(SolClientReturnCode::NotFound) {
+            break;
+        }
+        if SolClientReturnCode::from_i32(rc) != Some(SolClientReturnCode::Ok) {
+            return Err(last_error(parsed_rc(rc)));
+        }
+
+        items.push(read_field(&field)?);
+    }
+    Ok(items)
+}
@@ -1,14 +1,101 @@
 use crate::context::Context;
-use crate::message::{Message, OutboundMessage};
+use crate::error::{last_error, parsed_rc};
+use crate::message::{InboundMessage, Message, OutboundMessage};
 use crate::SessionError;
 use crate::SolClientReturnCode;
+use enum_primitive::*;
 use num_traits::FromPrimitive;
 use solace_rs_sys as ffi;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_void;
+use std::ptr;
+use std::time::Duration;
 use tracing::warn;
 
 type Result<T> = std::result::Result<T, SessionError>;
 
+// Boxed twice so that `user_p` is a thin, stable pointer: the outer `Box` is
+// what gets turned into the raw pointer handed to solClient, while the inner
+// `Box<dyn Fn>` is the (fat) trait object it points at.
+type RxCallback = Box<dyn Fn(InboundMessage) + Send>;
+
+extern "C" fn rx_msg_trampoline(
+    _opaque_session_p: ffi::solClient_opaqueSession_pt,
+    msg_p: ffi::solClient_opaqueMsg_pt,
+    user_p: *mut c_void,
+) -> ffi::solClient_rxMsgCallback_returnCode_t {
+    let callback = unsafe { &*(user_p as *const RxCallback) };
+
+    // The callback is handed ownership of the message (wrapped as an
+    // `InboundMessage`, which frees it on drop), so solClient must not also
+    // free it: we report `TAKE_MSG` rather than `OK`.
+    callback(InboundMessage::from_raw(msg_p));
+
+    ffi::SOLCLIENT_CALLBACK_TAKE_MSG as ffi::solClient_rxMsgCallback_returnCode_t
+}
+
+/// The correlation tag, if any, that solClient reports on a session event
+/// concerning a message (e.g. `Acknowledgement`, `Rejected`), read from the
+/// event's `correlation_p` as a C string. This is not the correlation ID set by
+/// `OutboundMessageBuilder::set_correlation_id`, which goes into the message's
+/// correlation-ID header and does not set this tag.
+pub type CorrelationId = String;
+
+enum_from_primitive! {
+    /// The `solClient_session_event_t` events this crate models, covering
+    /// connectivity changes, guaranteed-messaging acknowledgements, and
+    /// broker-initiated actions. Events outside this set are logged and not
+    /// passed to the session's `on_event` callback.
+    #[derive(Debug, PartialEq)]
+    #[repr(u32)]
+    pub enum SessionEvent {
+        UpNotice = ffi::SOLCLIENT_SESSION_EVENT_UP_NOTICE,
+        DownError = ffi::SOLCLIENT_SESSION_EVENT_DOWN_ERROR,
+        ConnectFailedError = ffi::SOLCLIENT_SESSION_EVENT_CONNECT_FAILED_ERROR,
+        Rejected = ffi::SOLCLIENT_SESSION_EVENT_REJECTED_MSG_ERROR,
+        SubscriptionError = ffi::SOLCLIENT_SESSION_EVENT_SUBSCRIPTION_ERROR,
+        RxMsgTooBigError = ffi::SOLCLIENT_SESSION_EVENT_RX_MSG_TOO_BIG_ERROR,
+        Acknowledgement = ffi::SOLCLIENT_SESSION_EVENT_ACKNOWLEDGEMENT,
+        CanSend = ffi::SOLCLIENT_SESSION_EVENT_CAN_SEND,
+        ReconnectingNotice = ffi::SOLCLIENT_SESSION_EVENT_RECONNECTING_NOTICE,
+        ReconnectedNotice = ffi::SOLCLIENT_SESSION_EVENT_RECONNECTED_NOTICE,
+        ProvisionError = ffi::SOLCLIENT_SESSION_EVENT_PROVISION_ERROR,
+        SubscriptionOk = ffi::SOLCLIENT_SESSION_EVENT_SUBSCRIPTION_OK,
+        VirtualRouterNameChanged = ffi::SOLCLIENT_SESSION_EVENT_VIRTUAL_ROUTER_NAME_CHANGED,
+        ModifypropFail = ffi::SOLCLIENT_SESSION_EVENT_MODIFYPROP_FAIL,
+        ModifypropOk = ffi::SOLCLIENT_SESSION_EVENT_MODIFYPROP_OK,
+        RepublishUnackedMessages = ffi::SOLCLIENT_SESSION_EVENT_REPUBLISH_UNACKED_MESSAGES,
+    }
+}
+
+type EventCallback = Box<dyn Fn(SessionEvent, Option<CorrelationId>) + Send>;
+
+extern "C" fn event_trampoline(
+    _opaque_session_p: ffi::solClient_opaqueSession_pt,
+    event_info_p: ffi::solClient_session_eventCallbackInfo_pt,
+    user_p: *mut c_void,
+) {
+    let callback = unsafe { &*(user_p as *const EventCallback) };
+    let event_info = unsafe { &*event_info_p };
+
+    let Some(event) = SessionEvent::from_u32(event_info.sessionEvent as u32) else {
+        warn!("received unknown session event: {}", event_info.sessionEvent);
+        return;
+    };
+
+    let correlation_id = if event_info.correlation_p.is_null() {
+        None
+    } else {
+        Some(
+            unsafe { CStr::from_ptr(event_info.correlation_p as *const i8) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    };
+
+    callback(event, correlation_id);
+}
+
 pub struct Session {
     // Pointer to session
     // This pointer must never be allowed to leave the struct
@@ -17,20 +104,167 @@ pub struct Session {
     // reference counting via the `Drop` trait.
     #[allow(dead_code)]
     pub(crate) context: Context,
+    // The boxed rx-message callback, if one was registered when the session
+    // was created. Kept alive for as long as the session is, since solClient
+    // holds a raw pointer to it (`user_p`) for the lifetime of the session;
+    // freed in `Drop`.
+    pub(crate) rx_callback: Option<*mut RxCallback>,
+    // The boxed session-event callback, if one was registered when the
+    // session was created. Same lifetime rules as `rx_callback`.
+    pub(crate) event_callback: Option<*mut EventCallback>,
 }
 
 unsafe impl Send for Session {}
 unsafe impl Sync for Session {}
 
+/// The handful of session properties every connection needs. Mirrors the
+/// `SOLCLIENT_SESSION_PROP_*` keys solClient expects as a flat, NULL-terminated
+/// `props` array.
+pub struct SessionProps<'a> {
+    pub host: &'a str,
+    pub vpn_name: &'a str,
+    pub username: &'a str,
+    pub password: &'a str,
+}
+
 impl Session {
+    /// Creates and connects a [`Session`] on `context`, optionally registering
+    /// an `on_message` callback to receive messages delivered to this
+    /// session's subscriptions (without it, `subscribe` has nowhere to
+    /// deliver the messages it admits) and an `on_event` callback to learn
+    /// about connectivity changes such as reconnects and disconnects; only
+    /// the events listed in [`SessionEvent`] reach it.
+    pub fn new<RxF, EventF>(
+        context: Context,
+        props: SessionProps,
+        on_message: Option<RxF>,
+        on_event: Option<EventF>,
+    ) -> Result<Self>
+    where
+        RxF: Fn(InboundMessage) + Send + 'static,
+        EventF: Fn(SessionEvent, Option<CorrelationId>) + Send + 'static,
+    {
+        let host = CString::new(props.host)?;
+        let vpn_name = CString::new(props.vpn_name)?;
+        let username = CString::new(props.username)?;
+        let password = CString::new(props.password)?;
+
+        let session_props: [*const std::os::raw::c_char; 9] = [
+            ffi::SOLCLIENT_SESSION_PROP_HOST.as_ptr() as *const std::os::raw::c_char,
+            host.as_ptr(),
+            ffi::SOLCLIENT_SESSION_PROP_VPN_NAME.as_ptr() as *const std::os::raw::c_char,
+            vpn_name.as_ptr(),
+            ffi::SOLCLIENT_SESSION_PROP_USERNAME.as_ptr() as *const std::os::raw::c_char,
+            username.as_ptr(),
+            ffi::SOLCLIENT_SESSION_PROP_PASSWORD.as_ptr() as *const std::os::raw::c_char,
+            password.as_ptr(),
+            ptr::null(),
+        ];
+
+        let (rx_func, rx_user_p, rx_callback) = match on_message {
+            Some(on_message) => {
+                let (func, user_p, raw) = Self::wrap_rx_callback(on_message);
+                (func, user_p, Some(raw))
+            }
+            None => (None, ptr::null_mut(), None),
+        };
+
+        let (event_func, event_user_p, event_callback) = match on_event {
+            Some(on_event) => {
+                let (func, user_p, raw) = Self::wrap_event_callback(on_event);
+                (func, user_p, Some(raw))
+            }
+            None => (None, ptr::null_mut(), None),
+        };
+
+        let mut func_info = ffi::solClient_session_createFuncInfo {
+            rxInfo: ffi::solClient_session_createRxCallbackFuncInfo {
+                callback_p: rx_func,
+                user_p: rx_user_p,
+            },
+            eventInfo: ffi::solClient_session_createEventCallbackFuncInfo {
+                callback_p: event_func,
+                user_p: event_user_p,
+            },
+        };
+
+        let mut session_pt: ffi::solClient_opaqueSession_pt = ptr::null_mut();
+        let create_result = unsafe {
+            ffi::solClient_session_create(
+                session_props.as_ptr() as *mut *mut std::os::raw::c_char,
+                context._context_pt,
+                &mut session_pt,
+                &mut func_info,
+                std::mem::size_of::<ffi::solClient_session_createFuncInfo>(),
+            )
+        };
+
+        if SolClientReturnCode::from_i32(create_result) != Some(SolClientReturnCode::Ok) {
+            // the session was never created, so nothing will ever invoke these
+            // callbacks; free them here rather than leaking them.
+            if let Some(raw) = rx_callback {
+                unsafe { drop(Box::from_raw(raw)) };
+            }
+            if let Some(raw) = event_callback {
+                unsafe { drop(Box::from_raw(raw)) };
+            }
+            return Err(SessionError::SolClient(last_error(parsed_rc(create_result))));
+        }
+
+        Ok(Session {
+            _session_pt: session_pt,
+            context,
+            rx_callback,
+            event_callback,
+        })
+    }
+
+    /// Boxes `on_message` and returns the `rxMsgCallback` function pointer and
+    /// `user_p` pair to be placed on `solClient_session_createFuncInfo` when
+    /// creating the session, along with the raw pointer that must be stashed
+    /// in `Session::rx_callback` so it outlives every invocation of the
+    /// callback and is freed exactly once, on `Drop`.
+    pub(crate) fn wrap_rx_callback<F>(
+        on_message: F,
+    ) -> (ffi::solClient_session_rxMsgCallbackFunc_t, *mut c_void, *mut RxCallback)
+    where
+        F: Fn(InboundMessage) + Send + 'static,
+    {
+        let boxed: RxCallback = Box::new(on_message);
+        let raw = Box::into_raw(Box::new(boxed));
+        (Some(rx_msg_trampoline), raw as *mut c_void, raw)
+    }
+
+    /// Boxes `on_event` and returns the `eventCallback` function pointer and
+    /// `user_p` pair to be placed on `solClient_session_createFuncInfo` when
+    /// creating the session, along with the raw pointer that must be stashed
+    /// in `Session::event_callback` so it outlives every invocation of the
+    /// callback and is freed exactly once, on `Drop`.
+    pub(crate) fn wrap_event_callback<F>(
+        on_event: F,
+    ) -> (
+        ffi::solClient_session_eventCallbackFunc_t,
+        *mut c_void,
+        *mut EventCallback,
+    )
+    where
+        F: Fn(SessionEvent, Option<CorrelationId>) + Send + 'static,
+    {
+        let boxed: EventCallback = Box::new(on_event);
+        let raw = Box::into_raw(Box::new(boxed));
+        (Some(event_trampoline), raw as *mut c_void, raw)
+    }
+
     pub fn publish(&self, message: OutboundMessage) -> Result<()> {
         let send_message_result = unsafe {
             ffi::solClient_session_sendMsg(self._session_pt, message.get_raw_message_ptr())
         };
-        assert_eq!(
-            SolClientReturnCode::from_i32(send_message_result),
-            Some(SolClientReturnCode::Ok)
-        );
+
+        if SolClientReturnCode::from_i32(send_message_result) != Some(SolClientReturnCode::Ok) {
+            return Err(SessionError::SolClient(last_error(parsed_rc(
+                send_message_result,
+            ))));
+        }
 
         Ok(())
     }
@@ -67,6 +301,49 @@ impl Session {
         Ok(())
     }
 
+    /// Sends `message` and blocks until a reply tagged with its correlation
+    /// ID arrives or `timeout` elapses. The reply-to destination and
+    /// correlation ID are set by solClient on the outgoing message, mirroring
+    /// `send_reply` on the responder's side.
+    pub fn request(&self, message: OutboundMessage, timeout: Duration) -> Result<InboundMessage> {
+        let mut reply_msg_ptr: ffi::solClient_opaqueMsg_pt = ptr::null_mut();
+
+        let send_request_result = unsafe {
+            ffi::solClient_session_sendRequest(
+                self._session_pt,
+                message.get_raw_message_ptr(),
+                &mut reply_msg_ptr,
+                timeout.as_millis() as u32,
+            )
+        };
+
+        if SolClientReturnCode::from_i32(send_request_result) != Some(SolClientReturnCode::Ok) {
+            return Err(SessionError::SolClient(last_error(parsed_rc(
+                send_request_result,
+            ))));
+        }
+
+        Ok(InboundMessage::from_raw(reply_msg_ptr))
+    }
+
+    /// Sends `reply` back to the sender of `request`, copying the reply-to
+    /// destination and correlation ID from the inbound request.
+    pub fn send_reply(&self, request: &InboundMessage, reply: OutboundMessage) -> Result<()> {
+        let send_reply_result = unsafe {
+            ffi::solClient_session_sendReply(
+                self._session_pt,
+                request.get_raw_message_ptr(),
+                reply.get_raw_message_ptr(),
+            )
+        };
+
+        if SolClientReturnCode::from_i32(send_reply_result) != Some(SolClientReturnCode::Ok) {
+            return Err(SessionError::SolClient(last_error(parsed_rc(
+                send_reply_result,
+            ))));
+        }
+        Ok(())
+    }
 }
 
 impl Drop for Session {
@@ -75,5 +352,15 @@ impl Drop for Session {
         if SolClientReturnCode::from_i32(session_free_result) != Some(SolClientReturnCode::Ok) {
             warn!("session was not dropped properly");
         }
+
+        if let Some(rx_callback) = self.rx_callback.take() {
+            // Safe to drop now: solClient has destroyed the session and will
+            // not invoke the callback again.
+            unsafe { drop(Box::from_raw(rx_callback)) };
+        }
+
+        if let Some(event_callback) = self.event_callback.take() {
+            unsafe { drop(Box::from_raw(event_callback)) };
+        }
     }
 }